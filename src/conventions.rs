@@ -0,0 +1,105 @@
+use crate::validator::_block_grid;
+use gdal::raster::RasterBand;
+use gdal::{Dataset, Metadata};
+
+/// Metadata domains GDAL has used for block metadata, in probing order
+const BLOCK_DOMAINS: [&str; 2] = ["TIFF", ""];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Metadata domain under which the linked GDAL exposes block information
+///
+/// Only the domain is detected: the `BLOCK_OFFSET_x_y` and `BLOCK_SIZE_x_y`
+/// key names are the same in every GDAL version.
+pub struct MetadataConventions {
+    /// Metadata domain where block items were found, `""` being the default domain.
+    /// Items missing from it are still looked up in the other known domains
+    pub block_domain: &'static str,
+}
+
+impl Default for MetadataConventions {
+    fn default() -> Self {
+        MetadataConventions {
+            block_domain: BLOCK_DOMAINS[0],
+        }
+    }
+}
+
+impl MetadataConventions {
+    /// Reads the offset metadata item of block (`x`, `y`)
+    pub fn block_offset(&self, band: &RasterBand, x: usize, y: usize) -> Option<String> {
+        self.block_item(band, format!("BLOCK_OFFSET_{x}_{y}").as_str())
+    }
+
+    /// Reads the byte count metadata item of block (`x`, `y`)
+    pub fn block_size(&self, band: &RasterBand, x: usize, y: usize) -> Option<String> {
+        self.block_item(band, format!("BLOCK_SIZE_{x}_{y}").as_str())
+    }
+
+    /// Reads a block metadata item from the detected domain first, then from
+    /// the other known domains, so it is only missing if absent from all of them
+    fn block_item(&self, band: &RasterBand, key: &str) -> Option<String> {
+        let others = BLOCK_DOMAINS
+            .into_iter()
+            .filter(|&d| d != self.block_domain);
+        std::iter::once(self.block_domain)
+            .chain(others)
+            .find_map(|domain| band.metadata_item(key, domain))
+    }
+}
+
+/// Probes which metadata domain the linked GDAL uses for block information
+///
+/// The first and last blocks of the first band are looked up in every known
+/// domain, as either may be sparse. Falls back to the `TIFF` domain when no
+/// block metadata is found at all; lookups then still try every domain.
+///
+/// # Arguments
+/// * `dst` - GDAL Dataset to probe
+pub fn detect_metadata_conventions(dst: &Dataset) -> MetadataConventions {
+    let default = MetadataConventions::default();
+    let Ok(band) = dst.rasterband(1) else {
+        return default;
+    };
+    let (xblocks, yblocks) = _block_grid(&band);
+    let last_block = (xblocks.saturating_sub(1), yblocks.saturating_sub(1));
+    BLOCK_DOMAINS
+        .into_iter()
+        .find(|&domain| {
+            [(0, 0), last_block].iter().any(|&(x, y)| {
+                let key = format!("BLOCK_OFFSET_{x}_{y}");
+                band.metadata_item(key.as_str(), domain).is_some()
+            })
+        })
+        .map_or(default, |block_domain| MetadataConventions { block_domain })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_detect_metadata_conventions() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let dst = Dataset::open(current_dir).unwrap();
+        let conventions = detect_metadata_conventions(&dst);
+        let band = dst.rasterband(1).unwrap();
+        assert!(conventions.block_offset(&band, 0, 0).is_some());
+        assert!(conventions.block_size(&band, 0, 0).is_some());
+    }
+
+    #[test]
+    fn test_block_item_falls_back_to_other_domains() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let dst = Dataset::open(current_dir).unwrap();
+        let band = dst.rasterband(1).unwrap();
+        // Whichever domain was detected, starting from every other one still finds the items
+        for block_domain in BLOCK_DOMAINS {
+            let conventions = MetadataConventions { block_domain };
+            assert!(conventions.block_offset(&band, 0, 0).is_some());
+            assert!(conventions.block_size(&band, 0, 0).is_some());
+        }
+    }
+}
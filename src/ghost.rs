@@ -0,0 +1,154 @@
+use crate::tiff::TiffHeader;
+use crate::validator::ValidateCOGError;
+use crate::vsi::{VSIFile, Whence};
+use std::str;
+
+/// Prefix of the line that opens the GDAL ghost area
+const GHOST_SIZE_PREFIX: &str = "GDAL_STRUCTURAL_METADATA_SIZE=";
+/// Length of the `GDAL_STRUCTURAL_METADATA_SIZE=XXXXXX bytes\n` line
+const GHOST_SIZE_LINE_LEN: usize = GHOST_SIZE_PREFIX.len() + 6 + " bytes\n".len();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// GDAL structural metadata (the "ghost area") stored between the TIFF header and the first IFD
+pub struct GhostHeader {
+    /// Size of the key/value block declared by `GDAL_STRUCTURAL_METADATA_SIZE`
+    pub declared_size: usize,
+    /// Key/value items in file order, e.g. `LAYOUT=IFDS_BEFORE_DATA`
+    pub items: Vec<(String, String)>,
+}
+
+impl GhostHeader {
+    /// Reads the ghost area if the file has one
+    ///
+    /// # Arguments
+    /// * `f` - VSI file handle
+    /// * `header` - TIFF header of the file
+    ///
+    /// # Returns
+    /// * `Ok(None)` if the file has no ghost area
+    /// * `Err(ValidateCOGError::GhostSizeMismatch)` if the declared size does not frame the key/value block
+    pub fn read(f: &VSIFile, header: &TiffHeader) -> Result<Option<Self>, ValidateCOGError> {
+        let start = header.size();
+        let block_start = start + GHOST_SIZE_LINE_LEN as u64;
+        if block_start > header.first_ifd_offset {
+            return Ok(None);
+        }
+        let mut line = [0u8; GHOST_SIZE_LINE_LEN];
+        f.read_exact_at(&mut line, start, Whence::SeekSet)?;
+        let declared_size = match _parse_size_line(&line)? {
+            Some(size) => size,
+            None => return Ok(None),
+        };
+
+        // Never read past the first IFD: a declared size overlapping it
+        // is reported as a mismatch by the parser instead
+        let available = (header.first_ifd_offset - block_start) as usize;
+        let mut block = vec![0u8; declared_size.min(available)];
+        if !block.is_empty() {
+            f.read_exact_at(&mut block, block_start, Whence::SeekSet)?;
+        }
+        GhostHeader::parse(declared_size, &block).map(Some)
+    }
+
+    /// Parses the key/value block that follows the size line
+    ///
+    /// The block must consist of `KEY=VALUE\n` lines, optionally followed by
+    /// space padding, and must consume exactly `declared_size` bytes.
+    pub fn parse(declared_size: usize, block: &[u8]) -> Result<Self, ValidateCOGError> {
+        let mut items = Vec::new();
+        let mut parsed = 0;
+        while let Some(len) = block[parsed..].iter().position(|&b| b == b'\n') {
+            let item = str::from_utf8(&block[parsed..parsed + len])
+                .ok()
+                .and_then(|line| line.split_once('='))
+                .filter(|(key, _)| !key.is_empty());
+            match item {
+                Some((key, value)) => items.push((key.to_string(), value.to_string())),
+                None => break,
+            }
+            parsed += len + 1;
+        }
+        if block[parsed..].iter().all(|&b| b == b' ') {
+            parsed = block.len();
+        }
+        if parsed != declared_size {
+            return Err(ValidateCOGError::GhostSizeMismatch {
+                declared: declared_size,
+                parsed,
+            });
+        }
+        Ok(GhostHeader {
+            declared_size,
+            items,
+        })
+    }
+
+    /// Returns the value of a ghost area item
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parses the `GDAL_STRUCTURAL_METADATA_SIZE=XXXXXX bytes\n` line
+/// Returns `None` if the line is not a ghost area declaration
+fn _parse_size_line(line: &[u8]) -> Result<Option<usize>, ValidateCOGError> {
+    let Some(rest) = line.strip_prefix(GHOST_SIZE_PREFIX.as_bytes()) else {
+        return Ok(None);
+    };
+    let (digits, suffix) = rest.split_at(6);
+    if suffix != b" bytes\n" {
+        return Err(ValidateCOGError::InvalidGhostHeaderError);
+    }
+    str::from_utf8(digits)
+        .ok()
+        .and_then(|digits| digits.parse::<usize>().ok())
+        .map(Some)
+        .ok_or(ValidateCOGError::InvalidGhostHeaderError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: &[u8] =
+        b"LAYOUT=IFDS_BEFORE_DATA\nBLOCK_ORDER=ROW_MAJOR\nKNOWN_INCOMPATIBLE_EDITION=NO\n ";
+
+    #[test]
+    fn test_parse_size_line() {
+        let line = b"GDAL_STRUCTURAL_METADATA_SIZE=000140 bytes\n";
+        assert_eq!(_parse_size_line(line).unwrap(), Some(140));
+        let other = b"Not a GDAL structural metadata line........";
+        assert_eq!(_parse_size_line(other).unwrap(), None);
+        let malformed = b"GDAL_STRUCTURAL_METADATA_SIZE=00014x bytes\n";
+        assert!(_parse_size_line(malformed).is_err());
+    }
+
+    #[test]
+    fn test_parse_ghost_block() {
+        let ghost = GhostHeader::parse(BLOCK.len(), BLOCK).unwrap();
+        assert_eq!(ghost.items.len(), 3);
+        assert_eq!(ghost.get("LAYOUT"), Some("IFDS_BEFORE_DATA"));
+        assert_eq!(ghost.get("KNOWN_INCOMPATIBLE_EDITION"), Some("NO"));
+        assert_eq!(ghost.get("BLOCK_LEADER"), None);
+    }
+
+    #[test]
+    fn test_parse_ghost_block_size_mismatch() {
+        // Declared size cuts the last item in half
+        let truncated = &BLOCK[..BLOCK.len() - 10];
+        assert!(matches!(
+            GhostHeader::parse(truncated.len(), truncated),
+            Err(ValidateCOGError::GhostSizeMismatch { parsed: 46, .. })
+        ));
+        // Declared size runs into bytes that are not key/value lines
+        let mut overlong = BLOCK.to_vec();
+        overlong.extend_from_slice(&[0x2a, 0x00, 0x08, 0x00]);
+        assert!(matches!(
+            GhostHeader::parse(overlong.len(), &overlong),
+            Err(ValidateCOGError::GhostSizeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::tiff::{read_ifds, IfdTag, TiffHeader};
+use crate::validator::ValidateCOGError;
+use crate::vsi::{FileAccessMode, VSIFile};
+use gdal::raster::RasterCreationOptions;
+use gdal::{Dataset, DatasetOptions, DriverManager};
+use std::path::Path;
+
+/// Writes an overview level out as a standalone GeoTIFF for visual inspection
+///
+/// # Arguments
+/// * `file_path` - Path to the COG
+/// * `level` - Index of the overview to extract, 0 being the largest
+/// * `out` - Path of the GeoTIFF to write
+///
+/// # Returns
+/// * `Ok(())` once the overview has been written
+/// * `Err(ValidateCOGError::OverviewLevelError)` if the file has no such overview
+pub fn extract_overview<P: AsRef<Path>>(
+    file_path: &P,
+    level: usize,
+    out: &Path,
+) -> Result<(), ValidateCOGError> {
+    let dst = Dataset::open(file_path)?;
+    let count = dst.rasterband(1)?.overview_count()? as usize;
+    if level >= count {
+        return Err(ValidateCOGError::OverviewLevelError { level, count });
+    }
+
+    // Reopening with OVERVIEW_LEVEL exposes the overview as the full resolution
+    // image, so georeferencing and masks are carried over by the copy
+    let overview_level = format!("OVERVIEW_LEVEL={}", level);
+    let ovr_dst = Dataset::open_ex(
+        file_path,
+        DatasetOptions {
+            open_options: Some(&[overview_level.as_str()]),
+            ..Default::default()
+        },
+    )?;
+    let driver = DriverManager::get_driver_by_name("GTiff")?;
+    ovr_dst.create_copy(&driver, out, &RasterCreationOptions::new())?;
+    Ok(())
+}
+
+/// Lists every tag of an IFD, like `tiffdump` does
+///
+/// # Arguments
+/// * `file_path` - Path to the TIFF file
+/// * `ifd_index` - Index of the IFD in the chain, 0 being the main image
+///
+/// # Returns
+/// * `Ok(Vec<IfdTag>)` with the entries of the IFD in file order
+/// * `Err(ValidateCOGError::IfdIndexError)` if the file has no such IFD
+pub fn dump_ifd_tags<P: AsRef<Path>>(
+    file_path: &P,
+    ifd_index: usize,
+) -> Result<Vec<IfdTag>, ValidateCOGError> {
+    let f = &VSIFile::vsi_fopenl(file_path.as_ref(), FileAccessMode::ReadBinary)?;
+    let header = TiffHeader::read(f)?;
+    let mut ifds = read_ifds(f, &header)?;
+    f.vsi_fclosel()?;
+    if ifd_index >= ifds.len() {
+        return Err(ValidateCOGError::IfdIndexError {
+            index: ifd_index,
+            count: ifds.len(),
+        });
+    }
+    Ok(ifds.swap_remove(ifd_index).tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_extract_missing_overview() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let out = env::temp_dir().join("PuertoRicoTropicalFruit_ovr.tif");
+        let result = extract_overview(&current_dir, 0, &out);
+        assert!(matches!(
+            result,
+            Err(ValidateCOGError::OverviewLevelError { level: 0, count: 0 })
+        ))
+    }
+
+    #[test]
+    fn test_extract_overview() -> Result<(), ValidateCOGError> {
+        let src = env::temp_dir().join("cog_validator_extract_src.tif");
+        let out = env::temp_dir().join("cog_validator_extract_ovr.tif");
+        {
+            let driver = DriverManager::get_driver_by_name("GTiff")?;
+            let mut dst = driver.create_with_band_type::<u8, _>(&src, 64, 48, 1)?;
+            dst.build_overviews("NEAREST", &[2, 4], &[])?;
+        }
+        extract_overview(&src, 0, &out)?;
+        let extracted = Dataset::open(&out)?;
+        assert_eq!(extracted.raster_size(), (32, 24));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dump_ifd_tags() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let tags = dump_ifd_tags(&current_dir, 0).unwrap();
+        assert_eq!(tags.len(), 19);
+        // ImageWidth and ImageLength
+        assert_eq!((tags[0].id, tags[0].value_or_offset), (256, 27));
+        assert_eq!((tags[1].id, tags[1].value_or_offset), (257, 11));
+        assert!(matches!(
+            dump_ifd_tags(&current_dir, 1),
+            Err(ValidateCOGError::IfdIndexError { index: 1, count: 1 })
+        ))
+    }
+}
@@ -1,5 +1,15 @@
 use std::path::Path;
 
+pub mod conventions;
+pub mod ghost;
+pub mod inspect;
+pub mod options;
+#[cfg(debug_assertions)]
+pub mod paranoid;
+pub mod progress;
+pub mod reference;
+pub mod report;
+pub mod tiff;
 pub mod validator;
 pub mod vsi;
 
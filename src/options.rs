@@ -0,0 +1,55 @@
+use crate::report::DataLayout;
+
+#[derive(Debug, Clone, Default)]
+/// Optional checks applied on top of the default COG validation
+pub struct ValidationOptions {
+    /// Require the tile data to be physically ordered with this layout
+    pub expected_data_layout: Option<DataLayout>,
+    /// Require images greater than 512xH or Wx512 to be tiled rather than stripped
+    pub require_tiling: bool,
+    /// Fail instead of warning when images greater than 512xH or Wx512 have no internal overviews
+    pub require_internal_overviews: bool,
+    /// Require every IFD, and its tag values stored outside of the entries,
+    /// to be located before the first tile data of any band
+    pub require_ifds_before_data: bool,
+    /// Require the ghost area to declare `BLOCK_LEADER=SIZE_AS_UINT4` and
+    /// `BLOCK_TRAILER=LAST_4_BYTES_REPEATED`
+    pub require_block_leader_trailer: bool,
+    /// Require the dataset to consist of a single file, without side-car files
+    pub require_single_file: bool,
+    /// Require block offsets to increase within each band
+    pub require_monotonic_offsets: bool,
+    /// Reject files whose ghost area declares `KNOWN_INCOMPATIBLE_EDITION=YES`
+    pub reject_incompatible_edition: bool,
+    /// Warn when the longest side of the smallest overview exceeds this many pixels
+    pub thumbnail_max_dim: Option<usize>,
+    /// Minimum number of overview levels the file must have
+    pub min_overview_levels: usize,
+}
+
+impl ValidationOptions {
+    /// Preset rejecting anything that is not strictly COG compliant
+    ///
+    /// Enables:
+    /// * `require_tiling`
+    /// * `require_internal_overviews`
+    /// * `require_ifds_before_data`
+    /// * `require_block_leader_trailer`
+    /// * `require_single_file`
+    /// * `require_monotonic_offsets`
+    /// * `reject_incompatible_edition`
+    ///
+    /// `expected_data_layout`, `thumbnail_max_dim` and `min_overview_levels` are left unset.
+    pub fn strict_spec() -> Self {
+        ValidationOptions {
+            require_tiling: true,
+            require_internal_overviews: true,
+            require_ifds_before_data: true,
+            require_block_leader_trailer: true,
+            require_single_file: true,
+            require_monotonic_offsets: true,
+            reject_incompatible_edition: true,
+            ..Default::default()
+        }
+    }
+}
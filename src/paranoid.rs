@@ -0,0 +1,192 @@
+use crate::options::ValidationOptions;
+use crate::report::ValidationReport;
+use crate::validator::{
+    _band_name, _block_grid, _ovr_name, validate_cloudgeotiff_with_options, ValidateCOGError,
+};
+use gdal::raster::RasterBand;
+use gdal::{Dataset, DatasetOptions, Metadata};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Sub-validation of the paranoid mode that detected a finding
+pub enum ParanoidStage {
+    /// Full structural validation with the strict COG spec preset
+    Structural,
+    /// Reopening the file with the GTiff driver and checking LAYOUT=COG
+    DriverReopen,
+    /// Decoding a sample of blocks of every band and overview
+    SampledDecode,
+}
+
+impl fmt::Display for ParanoidStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ParanoidStage::Structural => "structural validation",
+            ParanoidStage::DriverReopen => "driver reopen",
+            ParanoidStage::SampledDecode => "sampled decode",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A discrepancy found by one of the paranoid sub-validations
+pub struct ParanoidFinding {
+    /// Sub-validation that detected the discrepancy
+    pub stage: ParanoidStage,
+    /// Description of the discrepancy
+    pub message: String,
+}
+
+impl fmt::Display for ParanoidFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.stage, self.message)
+    }
+}
+
+#[derive(Debug, Default)]
+/// Aggregated outcome of every paranoid sub-validation
+pub struct ParanoidReport {
+    /// Report of the structural validation, if it succeeded
+    pub report: Option<ValidationReport>,
+    /// Discrepancies found by all sub-validations
+    pub findings: Vec<ParanoidFinding>,
+}
+
+impl ParanoidReport {
+    /// Returns true if no sub-validation found a discrepancy
+    pub fn is_valid(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    fn add(&mut self, stage: ParanoidStage, message: String) {
+        self.findings.push(ParanoidFinding { stage, message });
+    }
+}
+
+/// Validates a COG with every available check, only in debug and test builds
+///
+/// Runs the structural validation with [`ValidationOptions::strict_spec`],
+/// reopens the file with the GTiff driver to confirm GDAL sees a COG layout,
+/// and decodes a sample of blocks of every band and overview. Sub-validations
+/// never stop each other: every discrepancy is collected in the report.
+///
+/// # Arguments
+/// * `file_path` - Path to the file to validate
+pub fn validate_paranoid<P: AsRef<Path>>(file_path: &P) -> ParanoidReport {
+    let mut paranoid = ParanoidReport::default();
+    match validate_cloudgeotiff_with_options(file_path, &ValidationOptions::strict_spec()) {
+        Ok(report) => paranoid.report = Some(report),
+        Err(e) => paranoid.add(ParanoidStage::Structural, e.to_string()),
+    }
+    let reopened = Dataset::open_ex(
+        file_path,
+        DatasetOptions {
+            allowed_drivers: Some(&["GTiff"]),
+            ..Default::default()
+        },
+    );
+    match reopened {
+        Ok(dst) => {
+            _check_cog_layout(&dst, &mut paranoid);
+            if let Err(e) = _sampled_decode(&dst, &mut paranoid) {
+                paranoid.add(ParanoidStage::SampledDecode, e.to_string());
+            }
+        }
+        Err(e) => paranoid.add(ParanoidStage::DriverReopen, e.to_string()),
+    }
+    paranoid
+}
+
+/// Checks that GDAL recognizes the reopened file as having a COG layout
+fn _check_cog_layout(dst: &Dataset, paranoid: &mut ParanoidReport) {
+    let layout = dst.metadata_item("LAYOUT", "IMAGE_STRUCTURE");
+    if layout.as_deref() != Some("COG") {
+        let message = format!(
+            "GDAL reports LAYOUT={}, expected COG",
+            layout.unwrap_or_else(|| "missing".to_string())
+        );
+        paranoid.add(ParanoidStage::DriverReopen, message);
+    }
+}
+
+/// Decodes a sample of blocks of every band and overview
+fn _sampled_decode(dst: &Dataset, paranoid: &mut ParanoidReport) -> Result<bool, ValidateCOGError> {
+    for i in 1..=dst.raster_count() {
+        let band = &dst.rasterband(i)?;
+        _decode_band(&_band_name(i), band, paranoid);
+        for j in 0..band.overview_count()? {
+            let ovr_band = &band.overview(j as usize)?;
+            _decode_band(&_ovr_name(i, j as usize), ovr_band, paranoid);
+        }
+    }
+    Ok(true)
+}
+
+/// Decodes the first, middle and last blocks of a band
+fn _decode_band(band_name: &str, band: &RasterBand, paranoid: &mut ParanoidReport) {
+    let block_size = band.block_size();
+    let (xblocks, yblocks) = _block_grid(band);
+    for (x, y) in _sample_blocks(xblocks, yblocks) {
+        let decoded = band.actual_block_size(x, y).and_then(|window_size| {
+            let window = ((x * block_size.0) as isize, (y * block_size.1) as isize);
+            band.read_as::<f64>(window, window_size, window_size, None)
+        });
+        if let Err(e) = decoded {
+            let message = format!("{} block ({}, {}) failed to decode: {}", band_name, x, y, e);
+            paranoid.add(ParanoidStage::SampledDecode, message);
+        }
+    }
+}
+
+/// Returns the coordinates of the first, middle and last blocks of a grid, without duplicates
+fn _sample_blocks(xblocks: usize, yblocks: usize) -> Vec<(usize, usize)> {
+    let mut blocks = Vec::new();
+    if xblocks == 0 || yblocks == 0 {
+        return blocks;
+    }
+    for block in [
+        (0, 0),
+        (xblocks / 2, yblocks / 2),
+        (xblocks - 1, yblocks - 1),
+    ] {
+        if !blocks.contains(&block) {
+            blocks.push(block);
+        }
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_sample_blocks() {
+        assert_eq!(_sample_blocks(1, 1), vec![(0, 0)]);
+        assert_eq!(_sample_blocks(4, 3), vec![(0, 0), (2, 1), (3, 2)]);
+        assert!(_sample_blocks(0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_validate_paranoid() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let paranoid = validate_paranoid(&current_dir);
+        assert!(paranoid.is_valid(), "{:?}", paranoid.findings);
+        assert!(paranoid.report.is_some())
+    }
+
+    #[test]
+    fn test_validate_paranoid_not_cog() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit.tiff");
+        let paranoid = validate_paranoid(&current_dir);
+        assert!(paranoid
+            .findings
+            .iter()
+            .any(|finding| finding.stage == ParanoidStage::DriverReopen))
+    }
+}
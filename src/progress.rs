@@ -0,0 +1,96 @@
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Overall progress of a validation run across all bands, masks and overviews
+pub struct Progress {
+    /// Number of blocks validated so far
+    pub completed_blocks: usize,
+    /// Number of blocks the run will validate in total
+    pub total_blocks: usize,
+    /// Time spent since the block validation started
+    pub elapsed: Duration,
+    /// Rough estimate of the remaining time, extrapolated from the blocks done so far
+    pub estimated_remaining: Option<Duration>,
+}
+
+impl Progress {
+    /// Fraction of the blocks validated so far, between 0 and 1
+    pub fn fraction(&self) -> f64 {
+        if self.total_blocks == 0 {
+            return 1.0;
+        }
+        self.completed_blocks as f64 / self.total_blocks as f64
+    }
+}
+
+/// Counts validated blocks and reports the overall progress to a callback
+pub(crate) struct ProgressTracker<'a> {
+    total_blocks: usize,
+    completed_blocks: Cell<usize>,
+    start: Instant,
+    callback: RefCell<&'a mut dyn FnMut(&Progress)>,
+}
+
+impl<'a> ProgressTracker<'a> {
+    pub(crate) fn new(total_blocks: usize, callback: &'a mut dyn FnMut(&Progress)) -> Self {
+        ProgressTracker {
+            total_blocks,
+            completed_blocks: Cell::new(0),
+            start: Instant::now(),
+            callback: RefCell::new(callback),
+        }
+    }
+
+    /// Records one more validated block and notifies the callback
+    pub(crate) fn block_done(&self) {
+        let completed_blocks = self.completed_blocks.get() + 1;
+        self.completed_blocks.set(completed_blocks);
+        let elapsed = self.start.elapsed();
+        let progress = Progress {
+            completed_blocks,
+            total_blocks: self.total_blocks,
+            elapsed,
+            estimated_remaining: _estimate_remaining(elapsed, completed_blocks, self.total_blocks),
+        };
+        (self.callback.borrow_mut())(&progress);
+    }
+}
+
+/// Extrapolates the remaining time from the average time per completed block
+fn _estimate_remaining(elapsed: Duration, completed: usize, total: usize) -> Option<Duration> {
+    if completed == 0 {
+        return None;
+    }
+    let remaining = total.saturating_sub(completed) as u32;
+    (elapsed / completed as u32).checked_mul(remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_remaining() {
+        let elapsed = Duration::from_secs(10);
+        assert_eq!(_estimate_remaining(elapsed, 0, 100), None);
+        assert_eq!(
+            _estimate_remaining(elapsed, 25, 100),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(_estimate_remaining(elapsed, 100, 100), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_progress_tracker() {
+        let mut seen = Vec::new();
+        let mut callback = |progress: &Progress| seen.push(progress.completed_blocks);
+        {
+            let tracker = ProgressTracker::new(3, &mut callback);
+            for _ in 0..3 {
+                tracker.block_done();
+            }
+        }
+        assert_eq!(seen, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::validator::ValidateCOGError;
+use gdal::{Dataset, Metadata};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Selects which structural attributes are compared against the reference file
+pub struct ReferenceMatchOptions {
+    /// Compare the block size of the first band
+    pub block_size: bool,
+    /// Compare the overview count and decimation factors
+    pub overviews: bool,
+    /// Compare the `COMPRESSION` image structure metadata
+    pub compression: bool,
+    /// Compare the band count, band data types and interleaving
+    pub bands: bool,
+}
+
+impl Default for ReferenceMatchOptions {
+    fn default() -> Self {
+        ReferenceMatchOptions {
+            block_size: true,
+            overviews: true,
+            compression: true,
+            bands: true,
+        }
+    }
+}
+
+/// Validates that a file has the same structure as a reference COG
+///
+/// # Arguments
+/// * `file_path` - Path to the file to validate
+/// * `reference` - Path to the reference COG
+/// * `options` - Attributes to compare
+///
+/// # Returns
+/// * `Ok(true)` if every selected attribute matches the reference
+/// * `Err(ValidateCOGError::ReferenceMismatch)` for the first attribute that differs
+pub fn validate_matches_reference<P: AsRef<Path>>(
+    file_path: &P,
+    reference: &Path,
+    options: &ReferenceMatchOptions,
+) -> Result<bool, ValidateCOGError> {
+    let expected = _structure(&Dataset::open(reference)?, options)?;
+    let found = _structure(&Dataset::open(file_path)?, options)?;
+    for ((attribute, expected_value), (_, actual)) in expected.into_iter().zip(found) {
+        if expected_value != actual {
+            return Err(ValidateCOGError::ReferenceMismatch {
+                attribute: attribute.to_string(),
+                reference: expected_value,
+                actual,
+            });
+        }
+    }
+    Ok(true)
+}
+
+/// Describes the selected structural attributes of a dataset as `(attribute, value)` pairs
+fn _structure(
+    dst: &Dataset,
+    options: &ReferenceMatchOptions,
+) -> Result<Vec<(&'static str, String)>, ValidateCOGError> {
+    let main_band = &dst.rasterband(1)?;
+    let mut attributes = Vec::new();
+    if options.block_size {
+        let (x, y) = main_band.block_size();
+        attributes.push(("block_size", format!("{}x{}", x, y)));
+    }
+    if options.overviews {
+        let ovr_count = main_band.overview_count()?;
+        let mut factors = Vec::new();
+        for i in 0..ovr_count {
+            let ovr_band = main_band.overview(i as usize)?;
+            factors.push(_overview_factor(main_band.x_size(), ovr_band.x_size()).to_string());
+        }
+        attributes.push(("overview_count", ovr_count.to_string()));
+        attributes.push(("overview_factors", factors.join(", ")));
+    }
+    if options.compression {
+        let compression = dst
+            .metadata_item("COMPRESSION", "IMAGE_STRUCTURE")
+            .unwrap_or_else(|| "NONE".to_string());
+        attributes.push(("compression", compression));
+    }
+    if options.bands {
+        let mut band_types = Vec::new();
+        for band in dst.rasterbands() {
+            band_types.push(band?.band_type().name());
+        }
+        let interleave = dst
+            .metadata_item("INTERLEAVE", "IMAGE_STRUCTURE")
+            .unwrap_or_default();
+        attributes.push(("band_count", dst.raster_count().to_string()));
+        attributes.push(("band_types", band_types.join(", ")));
+        attributes.push(("interleave", interleave));
+    }
+    Ok(attributes)
+}
+
+/// Returns the decimation factor of an overview, rounded to the nearest integer
+fn _overview_factor(main_size: usize, ovr_size: usize) -> usize {
+    (main_size + ovr_size / 2) / ovr_size.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_overview_factor() {
+        assert_eq!(_overview_factor(1024, 512), 2);
+        assert_eq!(_overview_factor(1000, 250), 4);
+        // Odd sizes are rounded up by GDAL when building overviews
+        assert_eq!(_overview_factor(1001, 501), 2);
+        assert_eq!(_overview_factor(1001, 126), 8);
+    }
+
+    #[test]
+    fn test_validate_matches_itself() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let options = ReferenceMatchOptions::default();
+        let result = validate_matches_reference(&current_dir, &current_dir, &options).unwrap();
+        assert!(result)
+    }
+
+    #[test]
+    fn test_validate_block_size_mismatch() {
+        let mut reference = env::current_dir().unwrap();
+        reference.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit.tiff");
+        let options = ReferenceMatchOptions::default();
+        assert!(matches!(
+            validate_matches_reference(&current_dir, &reference, &options),
+            Err(ValidateCOGError::ReferenceMismatch { attribute, .. }) if attribute == "block_size"
+        ));
+    }
+
+    #[test]
+    fn test_validate_compression_mismatch() {
+        let mut reference = env::current_dir().unwrap();
+        reference.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit.tiff");
+        let options = ReferenceMatchOptions {
+            block_size: false,
+            ..Default::default()
+        };
+        assert!(matches!(
+            validate_matches_reference(&current_dir, &reference, &options),
+            Err(ValidateCOGError::ReferenceMismatch { attribute, .. }) if attribute == "compression"
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ghost::GhostHeader;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+/// Enum representing non-fatal findings reported during Cloud Optimized GeoTIFF validation
+pub enum Warning {
+    #[error(
+        "The file is greater than 512xH or Wx512, it is recommended to include internal overviews"
+    )]
+    MissingOverviews,
+    #[error(
+        "{band_name} NoData value ({nodata}) falls inside the valid data range [{min}, {max}]"
+    )]
+    NoDataWithinValidRange {
+        band_name: String,
+        nodata: f64,
+        min: f64,
+        max: f64,
+    },
+    #[error("overview_{level} of floating-point data was resampled with NEAREST")]
+    NearestOnContinuousData { level: usize },
+    #[error("The smallest overview is {smallest_dim}px on its longest side, larger than the {max_dim}px thumbnail limit")]
+    NoThumbnailSizedOverview { smallest_dim: usize, max_dim: usize },
+}
+
+#[derive(Debug, Default)]
+/// Summary of a successful validation run
+pub struct ValidationReport {
+    /// Non-fatal findings collected while validating
+    pub warnings: Vec<Warning>,
+    /// Mask flags of every band and overview that was inspected
+    pub masks: Vec<MaskReport>,
+    /// GDAL structural metadata, if the file has a ghost area
+    pub ghost: Option<GhostHeader>,
+    /// Physical ordering of the base image data relative to the overview data
+    pub data_layout: DataLayout,
+}
+
+impl ValidationReport {
+    /// Records a warning in the report
+    pub fn warn(&mut self, warning: Warning) {
+        self.warnings.push(warning);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Physical ordering of the base image tile data relative to the overview tile data
+pub enum DataLayout {
+    /// No overview holds tile data
+    #[default]
+    NoOverviews,
+    /// Only the overviews hold tile data, every base image tile being sparse
+    OverviewsOnly,
+    /// All base image tiles precede the overview tiles
+    BaseFirst,
+    /// All overview tiles precede the base image tiles, as written by GDAL
+    OverviewsFirst,
+    /// Base image and overview tiles are mixed
+    Interleaved,
+}
+
+impl fmt::Display for DataLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DataLayout::NoOverviews => "no overviews",
+            DataLayout::OverviewsOnly => "overviews only",
+            DataLayout::BaseFirst => "base image first",
+            DataLayout::OverviewsFirst => "overviews first",
+            DataLayout::Interleaved => "interleaved",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Mask flag combination reported by GDAL for a band
+pub struct MaskFlags {
+    /// `GMF_ALL_VALID`: there are no invalid pixels
+    pub all_valid: bool,
+    /// `GMF_PER_DATASET`: the mask band is shared between all bands
+    pub per_dataset: bool,
+    /// `GMF_ALPHA`: the mask band is actually an alpha band
+    pub alpha: bool,
+    /// `GMF_NODATA`: the mask is generated from the NoData value
+    pub nodata: bool,
+}
+
+impl MaskFlags {
+    /// Returns true if the band has its own mask band (no flag set)
+    pub fn is_per_band(&self) -> bool {
+        !(self.all_valid || self.per_dataset || self.alpha || self.nodata)
+    }
+}
+
+impl fmt::Display for MaskFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_per_band() {
+            return write!(f, "per-band mask");
+        }
+        let names: Vec<&str> = [
+            (self.all_valid, "GMF_ALL_VALID"),
+            (self.per_dataset, "GMF_PER_DATASET"),
+            (self.alpha, "GMF_ALPHA"),
+            (self.nodata, "GMF_NODATA"),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, name)| *name)
+        .collect();
+        write!(f, "{}", names.join(" | "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Mask representation found for a single band or overview
+pub struct MaskReport {
+    /// Name of the band, as used in validation errors
+    pub band_name: String,
+    /// Mask flags reported by GDAL
+    pub flags: MaskFlags,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_flags_display() {
+        let per_band = MaskFlags::default();
+        assert!(per_band.is_per_band());
+        assert_eq!(per_band.to_string(), "per-band mask");
+
+        let alpha = MaskFlags {
+            per_dataset: true,
+            alpha: true,
+            ..Default::default()
+        };
+        assert!(!alpha.is_per_band());
+        assert_eq!(alpha.to_string(), "GMF_PER_DATASET | GMF_ALPHA");
+    }
+}
@@ -0,0 +1,369 @@
+use crate::validator::ValidateCOGError;
+use crate::vsi::{VSIFile, Whence};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use std::collections::HashSet;
+
+/// `Compression` tag
+pub const TAG_COMPRESSION: u16 = 259;
+/// `PhotometricInterpretation` tag
+pub const TAG_PHOTOMETRIC: u16 = 262;
+/// `SamplesPerPixel` tag
+pub const TAG_SAMPLES_PER_PIXEL: u16 = 277;
+/// `PlanarConfiguration` tag
+pub const TAG_PLANAR_CONFIG: u16 = 284;
+/// `TileWidth` tag
+pub const TAG_TILE_WIDTH: u16 = 322;
+/// `TileLength` tag
+pub const TAG_TILE_LENGTH: u16 = 323;
+/// `YCbCrSubSampling` tag
+pub const TAG_YCBCR_SUBSAMPLING: u16 = 530;
+
+/// IFDs with more entries than this are treated as corrupt
+const MAX_IFD_ENTRIES: u64 = 4096;
+/// Tags with more value bytes than this are treated as corrupt
+const MAX_TAG_BYTES: u64 = 1 << 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Byte order declared by the first two bytes of a TIFF file
+pub enum TiffByteOrder {
+    /// `II`
+    LittleEndian,
+    /// `MM`
+    BigEndian,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Fixed-size header found at the start of classic TIFF and BigTIFF files
+pub struct TiffHeader {
+    /// Byte order used by every multi-byte value in the file
+    pub byte_order: TiffByteOrder,
+    /// Whether the file is a BigTIFF (version 43) rather than a classic TIFF (version 42)
+    pub big_tiff: bool,
+    /// Offset of the first IFD
+    pub first_ifd_offset: u64,
+}
+
+impl TiffHeader {
+    /// Reads the TIFF header from the start of the file
+    ///
+    /// # Arguments
+    /// * `f` - VSI file handle
+    pub fn read(f: &VSIFile) -> Result<Self, ValidateCOGError> {
+        let mut buf = [0u8; 16];
+        f.read_exact_at(&mut buf[..8], 0, Whence::SeekSet)?;
+        let byte_order = match &buf[..2] {
+            b"II" => TiffByteOrder::LittleEndian,
+            b"MM" => TiffByteOrder::BigEndian,
+            _ => return Err(ValidateCOGError::NotGeoTIFFError),
+        };
+        let mut header = TiffHeader {
+            byte_order,
+            big_tiff: false,
+            first_ifd_offset: 0,
+        };
+        match header.read_u16(&buf[2..4]) {
+            42 => {
+                header.first_ifd_offset = header.read_u32(&buf[4..8]) as u64;
+            }
+            43 => {
+                f.read_exact_at(&mut buf[8..], 8, Whence::SeekSet)?;
+                if header.read_u16(&buf[4..6]) != 8 || header.read_u16(&buf[6..8]) != 0 {
+                    return Err(ValidateCOGError::NotGeoTIFFError);
+                }
+                header.big_tiff = true;
+                header.first_ifd_offset = header.read_u64(&buf[8..16]);
+            }
+            _ => return Err(ValidateCOGError::NotGeoTIFFError),
+        }
+        Ok(header)
+    }
+
+    /// Size of the header in bytes, which is where the GDAL ghost area starts
+    pub fn size(&self) -> u64 {
+        if self.big_tiff {
+            16
+        } else {
+            8
+        }
+    }
+
+    /// Reads a `u16` in the file byte order
+    pub fn read_u16(&self, buf: &[u8]) -> u16 {
+        match self.byte_order {
+            TiffByteOrder::LittleEndian => LittleEndian::read_u16(buf),
+            TiffByteOrder::BigEndian => BigEndian::read_u16(buf),
+        }
+    }
+
+    /// Reads a `u32` in the file byte order
+    pub fn read_u32(&self, buf: &[u8]) -> u32 {
+        match self.byte_order {
+            TiffByteOrder::LittleEndian => LittleEndian::read_u32(buf),
+            TiffByteOrder::BigEndian => BigEndian::read_u32(buf),
+        }
+    }
+
+    /// Reads a `u64` in the file byte order
+    pub fn read_u64(&self, buf: &[u8]) -> u64 {
+        match self.byte_order {
+            TiffByteOrder::LittleEndian => LittleEndian::read_u64(buf),
+            TiffByteOrder::BigEndian => BigEndian::read_u64(buf),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single entry of an Image File Directory
+pub struct IfdTag {
+    /// Tag number, e.g. 259 for `Compression`
+    pub id: u16,
+    /// TIFF field type, e.g. 3 for `SHORT`
+    pub field_type: u16,
+    /// Number of values
+    pub count: u64,
+    /// The value itself for single integer values stored in the entry,
+    /// otherwise the raw value/offset field (the offset of the values when
+    /// they do not fit in the entry)
+    pub value_or_offset: u64,
+    raw: [u8; 8], // Raw value/offset field, used to decode values stored inline
+}
+
+impl IfdTag {
+    /// Size in bytes of a single value of this field type
+    pub fn type_size(&self) -> Option<u64> {
+        match self.field_type {
+            1 | 2 | 6 | 7 => Some(1),              // BYTE, ASCII, SBYTE, UNDEFINED
+            3 | 8 => Some(2),                      // SHORT, SSHORT
+            4 | 9 | 11 | 13 => Some(4),            // LONG, SLONG, FLOAT, IFD
+            5 | 10 | 12 | 16 | 17 | 18 => Some(8), // RATIONAL, SRATIONAL, DOUBLE, LONG8, SLONG8, IFD8
+            _ => None,
+        }
+    }
+
+    /// Returns true if the values are stored in the entry itself
+    pub fn is_inline(&self, header: &TiffHeader) -> bool {
+        let width = if header.big_tiff { 8 } else { 4 };
+        self.type_size()
+            .is_some_and(|size| self.count.saturating_mul(size) <= width)
+    }
+
+    /// Reads the values of an unsigned integer tag (`BYTE`, `SHORT`, `LONG`, `IFD`, `LONG8` or `IFD8`)
+    ///
+    /// # Arguments
+    /// * `f` - VSI file handle
+    /// * `header` - TIFF header of the file
+    pub fn values(&self, f: &VSIFile, header: &TiffHeader) -> Result<Vec<u64>, ValidateCOGError> {
+        let size = match self.field_type {
+            1 | 3 | 4 | 13 | 16 | 18 => self.type_size().unwrap_or(1),
+            _ => {
+                return Err(ValidateCOGError::InvalidTagTypeError {
+                    id: self.id,
+                    field_type: self.field_type,
+                })
+            }
+        };
+        let len = match self.count.checked_mul(size) {
+            Some(len) if len <= MAX_TAG_BYTES => len as usize,
+            _ => {
+                return Err(ValidateCOGError::InvalidIfdError {
+                    offset: self.value_or_offset,
+                })
+            }
+        };
+        let data = if self.is_inline(header) {
+            self.raw[..len].to_vec()
+        } else {
+            let mut buf = vec![0u8; len];
+            f.read_exact_at(&mut buf, self.value_or_offset, Whence::SeekSet)?;
+            buf
+        };
+        Ok(data
+            .chunks(size as usize)
+            .map(|chunk| match size {
+                1 => chunk[0] as u64,
+                2 => header.read_u16(chunk) as u64,
+                4 => header.read_u32(chunk) as u64,
+                _ => header.read_u64(chunk),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+impl IfdTag {
+    /// Builds a single value entry stored inline in a little-endian file
+    pub(crate) fn inline(id: u16, field_type: u16, value: u64) -> Self {
+        IfdTag {
+            id,
+            field_type,
+            count: 1,
+            value_or_offset: value,
+            raw: value.to_le_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An Image File Directory
+pub struct Ifd {
+    /// Offset of the IFD in the file
+    pub offset: u64,
+    /// Entries of the IFD in file order
+    pub tags: Vec<IfdTag>,
+    /// Offset of the next IFD, or 0 for the last one
+    pub next_ifd_offset: u64,
+}
+
+impl Ifd {
+    /// Reads the IFD stored at `offset`, handling classic TIFF and BigTIFF entry widths
+    ///
+    /// # Arguments
+    /// * `f` - VSI file handle
+    /// * `header` - TIFF header of the file
+    /// * `offset` - Offset of the IFD
+    pub fn read(f: &VSIFile, header: &TiffHeader, offset: u64) -> Result<Self, ValidateCOGError> {
+        let (count_len, entry_len, next_len) = if header.big_tiff {
+            (8, 20, 8)
+        } else {
+            (2, 12, 4)
+        };
+        let mut buf = [0u8; 8];
+        f.read_exact_at(&mut buf[..count_len], offset, Whence::SeekSet)?;
+        let entry_count = if header.big_tiff {
+            header.read_u64(&buf)
+        } else {
+            header.read_u16(&buf) as u64
+        };
+        if entry_count == 0 || entry_count > MAX_IFD_ENTRIES {
+            return Err(ValidateCOGError::InvalidIfdError { offset });
+        }
+
+        let mut entries = vec![0u8; entry_count as usize * entry_len + next_len];
+        f.read_exact_at(&mut entries, offset + count_len as u64, Whence::SeekSet)?;
+        let (entries, next) = entries.split_at(entry_count as usize * entry_len);
+        let tags = entries
+            .chunks(entry_len)
+            .map(|entry| {
+                let mut raw = [0u8; 8];
+                let (count, field) = if header.big_tiff {
+                    (header.read_u64(&entry[4..12]), &entry[12..20])
+                } else {
+                    (header.read_u32(&entry[4..8]) as u64, &entry[8..12])
+                };
+                raw[..field.len()].copy_from_slice(field);
+                let mut tag = IfdTag {
+                    id: header.read_u16(&entry[0..2]),
+                    field_type: header.read_u16(&entry[2..4]),
+                    count,
+                    value_or_offset: if header.big_tiff {
+                        header.read_u64(field)
+                    } else {
+                        header.read_u32(field) as u64
+                    },
+                    raw,
+                };
+                if tag.count == 1 && tag.is_inline(header) {
+                    tag.value_or_offset = match tag.field_type {
+                        1 => raw[0] as u64,
+                        3 => header.read_u16(&raw) as u64,
+                        4 | 13 => header.read_u32(&raw) as u64,
+                        _ => tag.value_or_offset,
+                    };
+                }
+                tag
+            })
+            .collect();
+        let next_ifd_offset = if header.big_tiff {
+            header.read_u64(next)
+        } else {
+            header.read_u32(next) as u64
+        };
+        Ok(Ifd {
+            offset,
+            tags,
+            next_ifd_offset,
+        })
+    }
+
+    /// Returns the entry with the given tag number
+    pub fn tag(&self, id: u16) -> Option<&IfdTag> {
+        self.tags.iter().find(|tag| tag.id == id)
+    }
+
+    /// Returns the offsets of the tag values stored outside of the entries,
+    /// e.g. the `TileOffsets` and `TileByteCounts` arrays
+    pub fn value_offsets<'a>(&'a self, header: &'a TiffHeader) -> impl Iterator<Item = u64> + 'a {
+        self.tags
+            .iter()
+            .filter(|tag| tag.type_size().is_some() && !tag.is_inline(header))
+            .map(|tag| tag.value_or_offset)
+    }
+}
+
+/// Reads every IFD of the file by following the chain from the header
+///
+/// # Arguments
+/// * `f` - VSI file handle
+/// * `header` - TIFF header of the file
+pub fn read_ifds(f: &VSIFile, header: &TiffHeader) -> Result<Vec<Ifd>, ValidateCOGError> {
+    let mut ifds = Vec::new();
+    let mut visited = HashSet::new();
+    let mut offset = header.first_ifd_offset;
+    while offset != 0 {
+        if !visited.insert(offset) {
+            return Err(ValidateCOGError::InvalidIfdError { offset });
+        }
+        let ifd = Ifd::read(f, header, offset)?;
+        offset = ifd.next_ifd_offset;
+        ifds.push(ifd);
+    }
+    Ok(ifds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vsi::FileAccessMode;
+    use std::env;
+
+    #[test]
+    fn test_read_ifds() -> Result<(), ValidateCOGError> {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let f = VSIFile::vsi_fopenl(&current_dir, FileAccessMode::ReadBinary)?;
+        let header = TiffHeader::read(&f)?;
+        assert_eq!(header.byte_order, TiffByteOrder::LittleEndian);
+        assert!(!header.big_tiff);
+
+        let ifds = read_ifds(&f, &header)?;
+        assert_eq!(ifds.len(), 1);
+        assert_eq!(ifds[0].offset, header.first_ifd_offset);
+        let compression = ifds[0].tag(TAG_COMPRESSION).unwrap();
+        assert_eq!(compression.value_or_offset, 5);
+        assert_eq!(compression.values(&f, &header)?, vec![5]);
+        f.vsi_fclosel()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_offsets() {
+        let header = TiffHeader {
+            byte_order: TiffByteOrder::LittleEndian,
+            big_tiff: false,
+            first_ifd_offset: 8,
+        };
+        let tag = |id, count, value_or_offset| IfdTag {
+            id,
+            field_type: 4,
+            count,
+            value_or_offset,
+            raw: [0; 8],
+        };
+        let ifd = Ifd {
+            offset: 8,
+            tags: vec![tag(TAG_TILE_WIDTH, 1, 256), tag(324, 4, 1000)],
+            next_ifd_offset: 0,
+        };
+        assert_eq!(ifd.value_offsets(&header).collect::<Vec<_>>(), vec![1000]);
+    }
+}
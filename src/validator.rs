@@ -1,3 +1,12 @@
+use crate::conventions::{detect_metadata_conventions, MetadataConventions};
+use crate::ghost::GhostHeader;
+use crate::options::ValidationOptions;
+use crate::progress::{Progress, ProgressTracker};
+use crate::report::{DataLayout, MaskFlags, MaskReport, ValidationReport, Warning};
+use crate::tiff::{
+    read_ifds, Ifd, TiffHeader, TAG_COMPRESSION, TAG_PHOTOMETRIC, TAG_PLANAR_CONFIG,
+    TAG_SAMPLES_PER_PIXEL, TAG_TILE_LENGTH, TAG_TILE_WIDTH, TAG_YCBCR_SUBSAMPLING,
+};
 use crate::vsi::{FileAccessMode, VSIError, VSIFile, Whence};
 use gdal::raster::RasterBand;
 use gdal_sys::CSLDestroy;
@@ -24,6 +33,24 @@ pub enum ValidateCOGError {
     ExternalOvrError,
     #[error("The file is greater than 512xH or Wx512, but is not tiled")]
     NotTiledError,
+    #[error("The file is greater than 512xH or Wx512, but has no internal overviews")]
+    MissingOverviewsError,
+    #[error("The file has {found} overview levels, at least {required} are required")]
+    TooFewOverviewLevels { found: usize, required: usize },
+    #[error("The dataset is not self-contained, it also uses: {}", .files.join(", "))]
+    ExternalFilesError { files: Vec<String> },
+    #[error("IFD data at offset {ifd_offset} is located after tile data at offset {data_offset}")]
+    IfdsAfterDataError { ifd_offset: u64, data_offset: u64 },
+    #[error("Tile size {width}x{height} is not a multiple of 16")]
+    TileDimensionNotMultipleOf16 { width: u64, height: u64 },
+    #[error("JPEG compressed RGB image is misconfigured: {detail}")]
+    JpegYcbcrConfigError { detail: String },
+    #[error("Ghost area item {key} is {found}, expected {expected}")]
+    GhostItemError {
+        key: String,
+        expected: String,
+        found: String,
+    },
     #[error("BLOCK_OFFSET_{x}_{y} is empty")]
     EmptyOffsetError { x: usize, y: usize },
     #[error("{band_name} block ({x}, {y}) offset is less than previous block.")]
@@ -48,6 +75,43 @@ pub enum ValidateCOGError {
         x: usize,
         y: usize,
     },
+    #[error("The GDAL_STRUCTURAL_METADATA_SIZE declaration is malformed")]
+    InvalidGhostHeaderError,
+    #[error("GDAL_STRUCTURAL_METADATA_SIZE declares {declared} bytes, but the key/value block spans {parsed} bytes")]
+    GhostSizeMismatch { declared: usize, parsed: usize },
+    #[error("{attribute} does not match the reference: expected {reference}, found {actual}")]
+    ReferenceMismatch {
+        attribute: String,
+        reference: String,
+        actual: String,
+    },
+    #[error("IFD at offset {offset} is malformed")]
+    InvalidIfdError { offset: u64 },
+    #[error("Tag {id} has unsupported field type {field_type}")]
+    InvalidTagTypeError { id: u16, field_type: u16 },
+    #[error("Compression tag value {ifd_value} does not match GDAL's COMPRESSION={gdal_name}")]
+    CompressionTagMismatch { ifd_value: u16, gdal_name: String },
+    #[error("IFD {index} does not exist, the file has {count} IFDs")]
+    IfdIndexError { index: usize, count: usize },
+    #[error("Overview level {level} does not exist, the file has {count} overviews")]
+    OverviewLevelError { level: usize, count: usize },
+    #[error("Tile data layout is {found}, expected {expected}")]
+    UnexpectedDataLayout {
+        found: DataLayout,
+        expected: DataLayout,
+    },
+}
+
+/// State shared by the block-level checks of a validation run
+struct BlockContext<'a> {
+    /// VSI file handle
+    f: &'a VSIFile,
+    /// Optional checks to apply
+    options: &'a ValidationOptions,
+    /// Where the linked GDAL exposes block metadata
+    conventions: MetadataConventions,
+    /// Overall progress reported after each block
+    progress: ProgressTracker<'a>,
 }
 
 /// Validates if a given file is a valid Cloud Optimized GeoTIFF (COG)
@@ -59,12 +123,73 @@ pub enum ValidateCOGError {
 /// * `Ok(true)` if the file is a valid COG
 /// * `Err(ValidateCOGError)` if validation fails
 pub fn validate_cloudgeotiff<P: AsRef<Path>>(file_path: &P) -> Result<bool, ValidateCOGError> {
+    let report = validate_cloudgeotiff_report(file_path)?;
+    for warning in &report.warnings {
+        println!("Warning: {warning}");
+    }
+    Ok(true)
+}
+
+/// Validates a Cloud Optimized GeoTIFF (COG) and returns the collected findings
+///
+/// # Arguments
+/// * `file_path` - Path to the file to validate
+///
+/// # Returns
+/// * `Ok(ValidationReport)` with any warnings if the file is a valid COG
+/// * `Err(ValidateCOGError)` if validation fails
+pub fn validate_cloudgeotiff_report<P: AsRef<Path>>(
+    file_path: &P,
+) -> Result<ValidationReport, ValidateCOGError> {
+    validate_cloudgeotiff_with_options(file_path, &ValidationOptions::default())
+}
+
+/// Validates a Cloud Optimized GeoTIFF (COG) with additional optional checks
+///
+/// # Arguments
+/// * `file_path` - Path to the file to validate
+/// * `options` - Optional checks to apply on top of the default validation
+///
+/// # Returns
+/// * `Ok(ValidationReport)` with any warnings if the file is a valid COG
+/// * `Err(ValidateCOGError)` if validation fails
+pub fn validate_cloudgeotiff_with_options<P: AsRef<Path>>(
+    file_path: &P,
+    options: &ValidationOptions,
+) -> Result<ValidationReport, ValidateCOGError> {
+    validate_cloudgeotiff_with_progress(file_path, options, |_| {})
+}
+
+/// Validates a Cloud Optimized GeoTIFF (COG), reporting the overall progress
+///
+/// The total number of blocks across all bands, masks and overviews is
+/// computed up front, so `callback` receives an overall percentage and a
+/// rough ETA after each validated block.
+///
+/// # Arguments
+/// * `file_path` - Path to the file to validate
+/// * `options` - Optional checks to apply on top of the default validation
+/// * `callback` - Called with the overall progress after each block
+///
+/// # Returns
+/// * `Ok(ValidationReport)` with any warnings if the file is a valid COG
+/// * `Err(ValidateCOGError)` if validation fails
+pub fn validate_cloudgeotiff_with_progress<P, F>(
+    file_path: &P,
+    options: &ValidationOptions,
+    mut callback: F,
+) -> Result<ValidationReport, ValidateCOGError>
+where
+    P: AsRef<Path>,
+    F: FnMut(&Progress),
+{
     let dst = &Dataset::open(file_path)?;
     if dst.driver().short_name() != "GTiff" {
         return Err(ValidateCOGError::NotGeoTIFFError);
     };
-    _validate(dst, file_path.as_ref())?;
-    Ok(true)
+    let mut report = ValidationReport::default();
+    _validate(dst, file_path.as_ref(), options, &mut callback, &mut report)?;
+    Ok(report)
 }
 
 /// Internal validation function that performs the actual COG validation checks
@@ -72,7 +197,16 @@ pub fn validate_cloudgeotiff<P: AsRef<Path>>(file_path: &P) -> Result<bool, Vali
 /// # Arguments
 /// * `dst` - GDAL Dataset to validate
 /// * `file_path` - Path to the file being validated
-fn _validate(dst: &Dataset, file_path: &Path) -> Result<bool, ValidateCOGError> {
+/// * `options` - Optional checks to apply
+/// * `callback` - Called with the overall progress after each block
+/// * `report` - Report collecting warnings
+fn _validate(
+    dst: &Dataset,
+    file_path: &Path,
+    options: &ValidationOptions,
+    callback: &mut dyn FnMut(&Progress),
+    report: &mut ValidationReport,
+) -> Result<bool, ValidateCOGError> {
     let main_band = &dst.rasterband(1)?;
     let ovr_count = main_band.overview_count()?;
 
@@ -83,13 +217,264 @@ fn _validate(dst: &Dataset, file_path: &Path) -> Result<bool, ValidateCOGError>
         strings
     };
 
-    _check_main_band(main_band, ovr_count)?;
+    _check_main_band(main_band, ovr_count, options, report)?;
+    _check_overview_levels(ovr_count, options)?;
+    _check_nodata_range(dst, report)?;
+    _check_ovr_resampling(main_band, ovr_count, report)?;
+    _check_thumbnail_overview(main_band, ovr_count, options, report)?;
+    _check_single_file(&file_list, options)?;
     _check_external_ovr(file_list)?;
     let f = &VSIFile::vsi_fopenl(file_path, FileAccessMode::ReadBinary)?;
-    _validate_band(f, "Main resolution image", main_band)?;
-    _validate_mask_band(f, "Main resolution image", main_band)?;
-    _validate_ovr(f, main_band, ovr_count)?;
+    let header = TiffHeader::read(f)?;
+    report.ghost = GhostHeader::read(f, &header)?;
+    let ifds = read_ifds(f, &header)?;
+    let main_ifd = ifds.first().ok_or(ValidateCOGError::InvalidIfdError {
+        offset: header.first_ifd_offset,
+    })?;
+    _check_compression_tag(dst, main_ifd)?;
+    _check_tiled(main_band, main_ifd, options)?;
+    _check_tile_dimensions(&ifds)?;
+    _check_jpeg_ycbcr(&ifds)?;
+    _check_ghost_items(report.ghost.as_ref(), options)?;
+    // With PLANARCONFIG=SEPARATE every band has its own tiles,
+    // otherwise all bands share the tiles of the first band
+    let separate_planes = main_ifd
+        .tag(TAG_PLANAR_CONFIG)
+        .is_some_and(|tag| tag.value_or_offset == 2);
+    let ctx = &BlockContext {
+        f,
+        options,
+        conventions: detect_metadata_conventions(dst),
+        progress: ProgressTracker::new(_total_blocks(dst, separate_planes)?, callback),
+    };
+    let band_name = _band_name(1);
+    let mut base_offsets = _validate_band(ctx, &band_name, main_band)?;
+    let mask_offsets = _validate_mask_band(ctx, &band_name, main_band, true, report)?;
+    base_offsets.extend(mask_offsets);
+    let mut ovr_offsets = _validate_ovr(ctx, main_band, ovr_count, report)?;
+    let (band_offsets, band_ovr_offsets) =
+        _validate_other_bands(ctx, dst, separate_planes, report)?;
+    base_offsets.extend(band_offsets);
+    ovr_offsets.extend(band_ovr_offsets);
     f.vsi_fclosel()?;
+    report.data_layout = _data_layout(&base_offsets, &ovr_offsets);
+    _check_data_layout(report.data_layout, options)?;
+    _check_ifds_before_data(&header, &ifds, &base_offsets, &ovr_offsets, options)?;
+    Ok(true)
+}
+
+/// Checks that the dataset does not rely on any file besides itself
+/// (external overviews, masks or .aux.xml side-car files)
+fn _check_single_file(
+    file_list: &[String],
+    options: &ValidationOptions,
+) -> Result<bool, ValidateCOGError> {
+    if options.require_single_file && file_list.len() > 1 {
+        return Err(ValidateCOGError::ExternalFilesError {
+            files: file_list[1..].to_vec(),
+        });
+    }
+    Ok(true)
+}
+
+/// Checks that images greater than 512xH or Wx512 are stored as tiles rather than strips
+fn _check_tiled(
+    band: &RasterBand,
+    ifd: &Ifd,
+    options: &ValidationOptions,
+) -> Result<bool, ValidateCOGError> {
+    if options.require_tiling
+        && (band.x_size() > 512 || band.y_size() > 512)
+        && ifd.tag(TAG_TILE_WIDTH).is_none()
+    {
+        return Err(ValidateCOGError::NotTiledError);
+    }
+    Ok(true)
+}
+
+/// Checks that the tile dimensions of every tiled IFD are multiples of 16,
+/// as required by the TIFF 6.0 specification
+fn _check_tile_dimensions(ifds: &[Ifd]) -> Result<bool, ValidateCOGError> {
+    for ifd in ifds {
+        if let (Some(width), Some(height)) = (ifd.tag(TAG_TILE_WIDTH), ifd.tag(TAG_TILE_LENGTH)) {
+            let (width, height) = (width.value_or_offset, height.value_or_offset);
+            if width % 16 != 0 || height % 16 != 0 {
+                return Err(ValidateCOGError::TileDimensionNotMultipleOf16 { width, height });
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Checks that every pixel-interleaved JPEG compressed RGB IFD is stored as
+/// YCbCr and declares its chroma subsampling, otherwise colors are decoded wrongly
+///
+/// Band-interleaved files (`PlanarConfiguration=2`) cannot be stored as YCbCr
+/// and 3-sample images with another photometric (e.g. MINISBLACK) are not RGB,
+/// so both are skipped.
+fn _check_jpeg_ycbcr(ifds: &[Ifd]) -> Result<bool, ValidateCOGError> {
+    for ifd in ifds {
+        let value = |id| ifd.tag(id).map(|tag| tag.value_or_offset);
+        if value(TAG_COMPRESSION) != Some(7)
+            || value(TAG_SAMPLES_PER_PIXEL).unwrap_or(1) != 3
+            || value(TAG_PLANAR_CONFIG) == Some(2)
+        {
+            continue;
+        }
+        let subsampling = ifd.tag(TAG_YCBCR_SUBSAMPLING).is_some();
+        if let Some(detail) = _jpeg_ycbcr_issue(value(TAG_PHOTOMETRIC), subsampling) {
+            return Err(ValidateCOGError::JpegYcbcrConfigError {
+                detail: format!("IFD at offset {}: {}", ifd.offset, detail),
+            });
+        }
+    }
+    Ok(true)
+}
+
+/// Describes what is wrong with the photometric and subsampling tags of a JPEG RGB IFD
+fn _jpeg_ycbcr_issue(photometric: Option<u64>, subsampling: bool) -> Option<String> {
+    match photometric {
+        Some(2) => Some("PhotometricInterpretation is RGB, expected YCbCr".to_string()),
+        Some(6) if !subsampling => Some("YCbCrSubSampling tag is missing".to_string()),
+        _ => None,
+    }
+}
+
+/// Checks the items of the ghost area required by the options
+fn _check_ghost_items(
+    ghost: Option<&GhostHeader>,
+    options: &ValidationOptions,
+) -> Result<bool, ValidateCOGError> {
+    let mut required = Vec::new();
+    if options.require_block_leader_trailer {
+        required.push(("BLOCK_LEADER", "SIZE_AS_UINT4"));
+        required.push(("BLOCK_TRAILER", "LAST_4_BYTES_REPEATED"));
+    }
+    for (key, expected) in required {
+        let found = ghost.and_then(|g| g.get(key));
+        if found != Some(expected) {
+            return Err(ValidateCOGError::GhostItemError {
+                key: key.to_string(),
+                expected: expected.to_string(),
+                found: found.unwrap_or("missing").to_string(),
+            });
+        }
+    }
+    if options.reject_incompatible_edition {
+        let key = "KNOWN_INCOMPATIBLE_EDITION";
+        if let Some(found) = ghost.and_then(|g| g.get(key)).filter(|v| *v != "NO") {
+            return Err(ValidateCOGError::GhostItemError {
+                key: key.to_string(),
+                expected: "NO".to_string(),
+                found: found.to_string(),
+            });
+        }
+    }
+    Ok(true)
+}
+
+/// Checks that every IFD, including its tag values stored outside of the
+/// entries, is located before the first tile data of any band
+fn _check_ifds_before_data(
+    header: &TiffHeader,
+    ifds: &[Ifd],
+    base_offsets: &[u64],
+    ovr_offsets: &[u64],
+    options: &ValidationOptions,
+) -> Result<bool, ValidateCOGError> {
+    if !options.require_ifds_before_data {
+        return Ok(true);
+    }
+    let data_offset = base_offsets.iter().chain(ovr_offsets).min();
+    let ifd_offset = ifds
+        .iter()
+        .flat_map(|ifd| std::iter::once(ifd.offset).chain(ifd.value_offsets(header)))
+        .max();
+    if let (Some(&data_offset), Some(ifd_offset)) = (data_offset, ifd_offset) {
+        if ifd_offset > data_offset {
+            return Err(ValidateCOGError::IfdsAfterDataError {
+                ifd_offset,
+                data_offset,
+            });
+        }
+    }
+    Ok(true)
+}
+
+/// Counts the blocks validated by a run, over every band, overview and mask
+fn _total_blocks(dst: &Dataset, separate_planes: bool) -> Result<usize, ValidateCOGError> {
+    let mut total = 0;
+    for i in 1..=dst.raster_count() {
+        let band = dst.rasterband(i)?;
+        let mut levels = Vec::new();
+        for j in 0..band.overview_count()? {
+            levels.push(band.overview(j as usize)?);
+        }
+        levels.push(band);
+        for level in &levels {
+            if _tiles_are_validated(i, separate_planes) {
+                total += _block_count(level);
+            }
+            if _mask_is_validated(&_mask_flags(level)?, i == 1) {
+                total += _block_count(&level.open_mask_band()?);
+            }
+        }
+    }
+    Ok(total)
+}
+
+/// Number of blocks of a band
+fn _block_count(band: &RasterBand) -> usize {
+    let (xblocks, yblocks) = _block_grid(band);
+    xblocks * yblocks
+}
+
+/// Returns true if the tiles of the band with the given index are validated:
+/// all bands share the tiles of the first band unless stored in separate planes
+fn _tiles_are_validated(band_index: usize, separate_planes: bool) -> bool {
+    band_index == 1 || separate_planes
+}
+
+/// Returns true if the mask described by `flags` is validated: per-band masks
+/// always, per-dataset masks only through the first band as all bands share them
+fn _mask_is_validated(flags: &MaskFlags, first_band: bool) -> bool {
+    flags.is_per_band() || (first_band && flags.per_dataset)
+}
+
+/// Determines how the base image tile data is ordered relative to the overview tile data
+///
+/// # Arguments
+/// * `base_offsets` - Tile offsets of the main resolution image of every band and its masks
+/// * `ovr_offsets` - Tile offsets of all overviews of every band and their masks
+fn _data_layout(base_offsets: &[u64], ovr_offsets: &[u64]) -> DataLayout {
+    let (Some(ovr_min), Some(ovr_max)) = (ovr_offsets.iter().min(), ovr_offsets.iter().max())
+    else {
+        return DataLayout::NoOverviews;
+    };
+    // Every base image tile may be sparse
+    let (Some(base_min), Some(base_max)) = (base_offsets.iter().min(), base_offsets.iter().max())
+    else {
+        return DataLayout::OverviewsOnly;
+    };
+    if base_max < ovr_min {
+        DataLayout::BaseFirst
+    } else if ovr_max < base_min {
+        DataLayout::OverviewsFirst
+    } else {
+        DataLayout::Interleaved
+    }
+}
+
+/// Checks the tile data layout against the one required by the options
+fn _check_data_layout(
+    found: DataLayout,
+    options: &ValidationOptions,
+) -> Result<bool, ValidateCOGError> {
+    if let Some(expected) = options.expected_data_layout {
+        if found != expected {
+            return Err(ValidateCOGError::UnexpectedDataLayout { found, expected });
+        }
+    }
     Ok(true)
 }
 
@@ -107,67 +492,252 @@ fn _check_external_ovr(file_list: Vec<String>) -> Result<bool, ValidateCOGError>
 }
 
 /// Validates the main band properties including size and tiling
-fn _check_main_band(band: &RasterBand, ovr_count: i32) -> Result<bool, ValidateCOGError> {
+fn _check_main_band(
+    band: &RasterBand,
+    ovr_count: i32,
+    options: &ValidationOptions,
+    report: &mut ValidationReport,
+) -> Result<bool, ValidateCOGError> {
     if band.x_size() > 512 || band.y_size() > 512 {
         let block_size = band.block_size();
         if block_size.0 == band.x_size() && block_size.0 > 1024 {
             return Err(ValidateCOGError::NotTiledError);
         }
         if ovr_count == 0 {
-            // warning：
-            // The file is greater than 512xH or Wx512, it is recommended
-            // to include internal overviews"
-            println!("Warning: The file is greater than 512xH or Wx512, it is recommended to include internal overviews");
+            if options.require_internal_overviews {
+                return Err(ValidateCOGError::MissingOverviewsError);
+            }
+            report.warn(Warning::MissingOverviews);
+        }
+    }
+    Ok(true)
+}
+
+/// Checks that the file has at least the number of overview levels required by the options
+fn _check_overview_levels(
+    ovr_count: i32,
+    options: &ValidationOptions,
+) -> Result<bool, ValidateCOGError> {
+    let found = ovr_count as usize;
+    if found < options.min_overview_levels {
+        return Err(ValidateCOGError::TooFewOverviewLevels {
+            found,
+            required: options.min_overview_levels,
+        });
+    }
+    Ok(true)
+}
+
+/// Checks the NoData value of every band against its recorded statistics
+fn _check_nodata_range(
+    dst: &Dataset,
+    report: &mut ValidationReport,
+) -> Result<bool, ValidateCOGError> {
+    for i in 1..=dst.raster_count() {
+        let band = &dst.rasterband(i)?;
+        _check_band_nodata_range(&_band_name(i), band, report);
+    }
+    Ok(true)
+}
+
+/// Warns when the NoData value of an integer band lies strictly inside the
+/// range recorded by its `STATISTICS_MINIMUM`/`STATISTICS_MAXIMUM` metadata,
+/// meaning legitimate pixels may be masked out as nodata
+fn _check_band_nodata_range(band_name: &str, band: &RasterBand, report: &mut ValidationReport) {
+    if !band.band_type().is_integer() {
+        return;
+    }
+    let Some(nodata) = band.no_data_value() else {
+        return;
+    };
+    let statistic = |key: &str| {
+        band.metadata_item(key, "")
+            .and_then(|v| v.trim().parse::<f64>().ok())
+    };
+    if let (Some(min), Some(max)) = (
+        statistic("STATISTICS_MINIMUM"),
+        statistic("STATISTICS_MAXIMUM"),
+    ) {
+        if _nodata_within_range(nodata, min, max) {
+            report.warn(Warning::NoDataWithinValidRange {
+                band_name: band_name.to_string(),
+                nodata,
+                min,
+                max,
+            });
+        }
+    }
+}
+
+/// Warns when overviews of floating-point (typically continuous) data were
+/// built with nearest-neighbour resampling, which produces blocky overviews
+///
+/// The resampling method is read from the `RESAMPLING` item of the overview
+/// bands, which GDAL loads from the `GDAL_METADATA` tag of each overview IFD.
+/// GDAL itself only writes that item for `AVERAGE_BIT2GRAYSCALE` overviews,
+/// so the warning relies on writers that record the resampling method of
+/// every overview and is never raised for overviews built by GDAL.
+fn _check_ovr_resampling(
+    band: &RasterBand,
+    ovr_count: i32,
+    report: &mut ValidationReport,
+) -> Result<bool, ValidateCOGError> {
+    if !band.band_type().is_floating() {
+        return Ok(true);
+    }
+    for i in 0..ovr_count {
+        let ovr_band = &band.overview(i as usize)?;
+        let resampling = ovr_band
+            .metadata_item("RESAMPLING", "IMAGE_STRUCTURE")
+            .or_else(|| ovr_band.metadata_item("RESAMPLING", ""));
+        if resampling.is_some_and(|r| r.to_uppercase().starts_with("NEAREST")) {
+            report.warn(Warning::NearestOnContinuousData { level: i as usize });
         }
     }
     Ok(true)
 }
 
+/// Warns when even the smallest overview is too large to be used as a thumbnail
+fn _check_thumbnail_overview(
+    band: &RasterBand,
+    ovr_count: i32,
+    options: &ValidationOptions,
+    report: &mut ValidationReport,
+) -> Result<bool, ValidateCOGError> {
+    let Some(max_dim) = options.thumbnail_max_dim else {
+        return Ok(true);
+    };
+    let mut smallest_dim = band.x_size().max(band.y_size());
+    for i in 0..ovr_count {
+        let ovr_band = &band.overview(i as usize)?;
+        smallest_dim = smallest_dim.min(ovr_band.x_size().max(ovr_band.y_size()));
+    }
+    if smallest_dim > max_dim {
+        report.warn(Warning::NoThumbnailSizedOverview {
+            smallest_dim,
+            max_dim,
+        });
+    }
+    Ok(true)
+}
+
+/// Returns true if `nodata` lies strictly between `min` and `max`
+fn _nodata_within_range(nodata: f64, min: f64, max: f64) -> bool {
+    min < nodata && nodata < max
+}
+
+/// Cross-checks the raw `Compression` tag of the main image IFD against
+/// the `COMPRESSION` metadata reported by GDAL
+fn _check_compression_tag(dst: &Dataset, ifd: &Ifd) -> Result<bool, ValidateCOGError> {
+    let ifd_value = ifd
+        .tag(TAG_COMPRESSION)
+        .map_or(1, |tag| tag.value_or_offset as u16);
+    let Some(expected) = _compression_names(ifd_value) else {
+        // GDAL may know codecs this table does not
+        return Ok(true);
+    };
+    let gdal_name = dst
+        .metadata_item("COMPRESSION", "IMAGE_STRUCTURE")
+        .unwrap_or_else(|| "NONE".to_string());
+    // LERC is reported as LERC, LERC_DEFLATE or LERC_ZSTD
+    let matches = |name: &&str| gdal_name == *name || gdal_name.starts_with(&format!("{}_", name));
+    if !expected.iter().any(matches) {
+        return Err(ValidateCOGError::CompressionTagMismatch {
+            ifd_value,
+            gdal_name,
+        });
+    }
+    Ok(true)
+}
+
+/// Maps a TIFF `Compression` tag value to the names GDAL may report for it
+fn _compression_names(ifd_value: u16) -> Option<&'static [&'static str]> {
+    match ifd_value {
+        1 => Some(&["NONE"]),
+        2 => Some(&["CCITTRLE"]),
+        3 => Some(&["CCITTFAX3"]),
+        4 => Some(&["CCITTFAX4"]),
+        5 => Some(&["LZW"]),
+        6 => Some(&["OJPEG"]),
+        // JPEG images stored as YCbCr are reported as "YCbCr JPEG"
+        7 => Some(&["JPEG", "YCbCr JPEG"]),
+        8 | 32946 => Some(&["DEFLATE"]),
+        32773 => Some(&["PACKBITS"]),
+        34887 => Some(&["LERC"]),
+        34925 => Some(&["LZMA"]),
+        50000 => Some(&["ZSTD"]),
+        50001 => Some(&["WEBP"]),
+        50002 | 52546 => Some(&["JXL"]),
+        _ => None,
+    }
+}
+
 /// Validates a specific raster band by checking all its blocks
 /// 
 /// # Arguments
-/// * `f` - VSI file handle
+/// * `ctx` - File handle, options and metadata conventions of the run
 /// * `band_name` - Name of the band being validated
 /// * `band` - The raster band to validate
+///
+/// # Returns
+/// * Offsets of all blocks that hold data
 fn _validate_band(
-    f: &VSIFile,
+    ctx: &BlockContext,
     band_name: &str,
     band: &RasterBand,
-) -> Result<bool, ValidateCOGError> {
-    let block_size = band.block_size();
-    let yblocks = (band.y_size() + block_size.1 - 1) / block_size.1;
-    let xblocks = (band.x_size() + block_size.0 - 1) / block_size.0;
-    let last_offset = 0_u64;
+) -> Result<Vec<u64>, ValidateCOGError> {
+    let (xblocks, yblocks) = _block_grid(band);
+    let mut last_offset = 0_u64;
+    let mut offsets = Vec::new();
     for y in 0..yblocks {
         for x in 0..xblocks {
-            _validate_block(f, band_name, band, x, y, last_offset)?;
+            let offset = _validate_block(ctx, band_name, band, x, y, last_offset)?;
+            ctx.progress.block_done();
+            if offset > 0 {
+                if ctx.options.require_monotonic_offsets {
+                    last_offset = offset;
+                }
+                offsets.push(offset);
+            }
         }
     }
-    Ok(true)
+    Ok(offsets)
+}
+
+/// Number of blocks of a band along the x and y axes
+pub(crate) fn _block_grid(band: &RasterBand) -> (usize, usize) {
+    let block_size = band.block_size();
+    (
+        band.x_size().div_ceil(block_size.0),
+        band.y_size().div_ceil(block_size.1),
+    )
 }
 
 /// Validates a specific block within a band
 /// 
 /// # Arguments
-/// * `f` - VSI file handle
+/// * `ctx` - File handle, options and metadata conventions of the run
 /// * `band_name` - Name of the band being validated
 /// * `band` - The raster band containing the block
 /// * `x` - X coordinate of the block
 /// * `y` - Y coordinate of the block
 /// * `last_offset` - Offset of the previous block
+///
+/// # Returns
+/// * Offset of the block, or 0 if the block holds no data
 fn _validate_block(
-    f: &VSIFile,
+    ctx: &BlockContext,
     band_name: &str,
     band: &RasterBand,
     x: usize,
     y: usize,
     last_offset: u64,
-) -> Result<bool, ValidateCOGError> {
-    let offset = match band.metadata_item(format!("BLOCK_OFFSET_{x}_{y}").as_str(), "TIFF") {
+) -> Result<u64, ValidateCOGError> {
+    let offset = match ctx.conventions.block_offset(band, x, y) {
         Some(i) => i.parse::<u64>().unwrap_or(0),
         None => return Err(ValidateCOGError::EmptyOffsetError { x, y }),
     };
-    let byte_count = match band.metadata_item(format!("BLOCK_SIZE_{x}_{y}").as_str(), "TIFF") {
+    let byte_count = match ctx.conventions.block_size(band, x, y) {
         Some(i) => i.parse::<u64>().unwrap_or(0),
         None => return Err(ValidateCOGError::EmptyOffsetError { x, y }),
     };
@@ -179,10 +749,10 @@ fn _validate_block(
                 y,
             });
         };
-        _check_leader_size(f, band_name, x, y, offset, byte_count)?;
-        _check_trailer_bytes(f, band_name, x, y, offset, byte_count)?;
+        _check_leader_size(ctx.f, band_name, x, y, offset, byte_count)?;
+        _check_trailer_bytes(ctx.f, band_name, x, y, offset, byte_count)?;
     };
-    Ok(true)
+    Ok(offset)
 }
 
 /// Checks if the leader size matches the block byte count
@@ -235,28 +805,114 @@ fn _check_trailer_bytes(
     Ok(true)
 }
 
-/// Validates the mask band if present
+/// Validates the mask band if present, returning the offsets of its blocks
+/// Per-dataset masks are only validated through the first band (`first_band`)
 fn _validate_mask_band(
-    f: &VSIFile,
+    ctx: &BlockContext,
     band_name: &str,
     band: &RasterBand,
-) -> Result<bool, ValidateCOGError> {
-    if band.mask_flags()?.is_per_dataset() {
+    first_band: bool,
+    report: &mut ValidationReport,
+) -> Result<Vec<u64>, ValidateCOGError> {
+    let flags = _record_mask_flags(band_name, band, report)?;
+    if _mask_is_validated(&flags, first_band) {
         let mask_band = &band.open_mask_band()?;
-        _validate_band(f, band_name, mask_band)?;
+        return _validate_band(ctx, band_name, mask_band);
     }
-    Ok(true)
+    Ok(Vec::new())
+}
+
+/// Validates the remaining bands and their overviews: their tiles when the
+/// bands are stored in separate planes, and their per-band masks.
+/// Per-dataset masks are shared with the first band and already validated
+///
+/// # Returns
+/// * Offsets of the main resolution blocks and of the overview blocks that hold data
+fn _validate_other_bands(
+    ctx: &BlockContext,
+    dst: &Dataset,
+    separate_planes: bool,
+    report: &mut ValidationReport,
+) -> Result<(Vec<u64>, Vec<u64>), ValidateCOGError> {
+    let mut base_offsets = Vec::new();
+    let mut ovr_offsets = Vec::new();
+    for i in 2..=dst.raster_count() {
+        let band = &dst.rasterband(i)?;
+        let band_name = _band_name(i);
+        if _tiles_are_validated(i, separate_planes) {
+            base_offsets.extend(_validate_band(ctx, &band_name, band)?);
+        }
+        base_offsets.extend(_validate_mask_band(ctx, &band_name, band, false, report)?);
+        for j in 0..band.overview_count()? {
+            let ovr_band = &band.overview(j as usize)?;
+            let ovr = _ovr_name(i, j as usize);
+            if _tiles_are_validated(i, separate_planes) {
+                ovr_offsets.extend(_validate_band(ctx, &ovr, ovr_band)?);
+            }
+            ovr_offsets.extend(_validate_mask_band(ctx, &ovr, ovr_band, false, report)?);
+        }
+    }
+    Ok((base_offsets, ovr_offsets))
+}
+
+/// Adds the mask flags of a band to the report
+fn _record_mask_flags(
+    band_name: &str,
+    band: &RasterBand,
+    report: &mut ValidationReport,
+) -> Result<MaskFlags, ValidateCOGError> {
+    let flags = _mask_flags(band)?;
+    report.masks.push(MaskReport {
+        band_name: band_name.to_string(),
+        flags,
+    });
+    Ok(flags)
+}
+
+/// Reads the mask flags GDAL reports for a band
+fn _mask_flags(band: &RasterBand) -> Result<MaskFlags, ValidateCOGError> {
+    let gdal_flags = band.mask_flags()?;
+    Ok(MaskFlags {
+        all_valid: gdal_flags.is_all_valid(),
+        per_dataset: gdal_flags.is_per_dataset(),
+        alpha: gdal_flags.is_alpha(),
+        nodata: gdal_flags.is_nodata(),
+    })
 }
 
-/// Validates all overview bands
-fn _validate_ovr(f: &VSIFile, band: &RasterBand, ovr_count: i32) -> Result<bool, ValidateCOGError> {
+/// Validates all overview bands, returning the offsets of their blocks
+fn _validate_ovr(
+    ctx: &BlockContext,
+    band: &RasterBand,
+    ovr_count: i32,
+    report: &mut ValidationReport,
+) -> Result<Vec<u64>, ValidateCOGError> {
+    let mut offsets = Vec::new();
     for i in 0..ovr_count {
         let ovr_band = &band.overview(i as usize)?;
-        let ovr = format!("overview_{}", i);
-        _validate_band(f, ovr.as_str(), ovr_band)?;
-        _validate_mask_band(f, ovr.as_str(), ovr_band)?;
+        let ovr = _ovr_name(1, i as usize);
+        offsets.extend(_validate_band(ctx, ovr.as_str(), ovr_band)?);
+        offsets.extend(_validate_mask_band(ctx, &ovr, ovr_band, true, report)?);
+    }
+    Ok(offsets)
+}
+
+/// Name of the band with the given index, as used in errors and report entries
+pub(crate) fn _band_name(band_index: usize) -> String {
+    if band_index == 1 {
+        "Main resolution image".to_string()
+    } else {
+        format!("Band {}", band_index)
+    }
+}
+
+/// Name of an overview of the band with the given index, as used in errors and report entries
+pub(crate) fn _ovr_name(band_index: usize, level: usize) -> String {
+    if band_index == 1 {
+        format!("overview_{}", level)
+    } else {
+        format!("{} overview_{}", _band_name(band_index), level)
     }
-    Ok(true)
 }
 
 // Utility functions
@@ -295,3 +951,263 @@ where
     }
     ret_val
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tiff::{IfdTag, TiffByteOrder};
+    use gdal::DriverManager;
+    use std::env;
+
+    #[test]
+    fn test_nodata_within_range() {
+        assert!(_nodata_within_range(0.0, -10.0, 100.0));
+        assert!(!_nodata_within_range(0.0, 0.0, 255.0));
+        assert!(!_nodata_within_range(255.0, 0.0, 255.0));
+        assert!(!_nodata_within_range(-9999.0, 0.0, 4000.0));
+    }
+
+    #[test]
+    fn test_compression_names() {
+        assert_eq!(_compression_names(1), Some(&["NONE"][..]));
+        assert_eq!(_compression_names(5), Some(&["LZW"][..]));
+        assert_eq!(_compression_names(32946), Some(&["DEFLATE"][..]));
+        assert_eq!(_compression_names(34887), Some(&["LERC"][..]));
+        assert_eq!(_compression_names(65000), None);
+    }
+
+    #[test]
+    fn test_compression_names_jpeg() {
+        let names = _compression_names(7).unwrap();
+        assert!(names.contains(&"JPEG"));
+        assert!(names.contains(&"YCbCr JPEG"));
+    }
+
+    #[test]
+    fn test_jpeg_ycbcr_issue() {
+        assert_eq!(_jpeg_ycbcr_issue(Some(6), true), None);
+        assert!(_jpeg_ycbcr_issue(Some(6), false).is_some());
+        assert!(_jpeg_ycbcr_issue(Some(2), true).is_some());
+        // MINISBLACK with 3 samples is not RGB imagery
+        assert_eq!(_jpeg_ycbcr_issue(Some(1), false), None);
+    }
+
+    #[test]
+    fn test_check_jpeg_ycbcr() {
+        let jpeg_ifd = |photometric, planar_config, subsampling: bool| {
+            let mut tags = vec![
+                IfdTag::inline(TAG_COMPRESSION, 3, 7),
+                IfdTag::inline(TAG_PHOTOMETRIC, 3, photometric),
+                IfdTag::inline(TAG_SAMPLES_PER_PIXEL, 3, 3),
+                IfdTag::inline(TAG_PLANAR_CONFIG, 3, planar_config),
+            ];
+            if subsampling {
+                tags.push(IfdTag::inline(TAG_YCBCR_SUBSAMPLING, 3, 2));
+            }
+            Ifd {
+                offset: 8,
+                tags,
+                next_ifd_offset: 0,
+            }
+        };
+        assert!(_check_jpeg_ycbcr(&[jpeg_ifd(6, 1, true)]).is_ok());
+        assert!(matches!(
+            _check_jpeg_ycbcr(&[jpeg_ifd(2, 1, false)]),
+            Err(ValidateCOGError::JpegYcbcrConfigError { .. })
+        ));
+        assert!(matches!(
+            _check_jpeg_ycbcr(&[jpeg_ifd(6, 1, false)]),
+            Err(ValidateCOGError::JpegYcbcrConfigError { .. })
+        ));
+        // Band-interleaved files cannot be stored as YCbCr
+        assert!(_check_jpeg_ycbcr(&[jpeg_ifd(2, 2, false)]).is_ok());
+        // Three MINISBLACK samples are not RGB imagery
+        assert!(_check_jpeg_ycbcr(&[jpeg_ifd(1, 1, false)]).is_ok());
+    }
+
+    #[test]
+    fn test_check_ovr_resampling() -> Result<(), ValidateCOGError> {
+        let driver = DriverManager::get_driver_by_name("MEM")?;
+        let mut dst = driver.create_with_band_type::<f32, _>("", 64, 64, 1)?;
+        dst.build_overviews("NEAREST", &[2, 4], &[])?;
+        let band = dst.rasterband(1)?;
+        let mut report = ValidationReport::default();
+        _check_ovr_resampling(&band, 2, &mut report)?;
+        assert!(report.warnings.is_empty());
+
+        let mut ovr_band = band.overview(1)?;
+        ovr_band.set_metadata_item("RESAMPLING", "NEAREST", "")?;
+        _check_ovr_resampling(&band, 2, &mut report)?;
+        assert_eq!(
+            report.warnings,
+            vec![Warning::NearestOnContinuousData { level: 1 }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_ghost_items() {
+        let options = ValidationOptions::strict_spec();
+        let block = b"BLOCK_LEADER=SIZE_AS_UINT4\nKNOWN_INCOMPATIBLE_EDITION=YES\n";
+        let ghost = GhostHeader::parse(block.len(), block).unwrap();
+        assert!(matches!(
+            _check_ghost_items(Some(&ghost), &options),
+            Err(ValidateCOGError::GhostItemError { key, .. }) if key == "BLOCK_TRAILER"
+        ));
+        assert!(_check_ghost_items(None, &options).is_err());
+        assert!(_check_ghost_items(None, &ValidationOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_strict_spec() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let options = ValidationOptions::strict_spec();
+        let report = validate_cloudgeotiff_with_options(&current_dir, &options).unwrap();
+        assert!(report.warnings.is_empty())
+    }
+
+    #[test]
+    fn test_thumbnail_max_dim() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let options = ValidationOptions {
+            thumbnail_max_dim: Some(16),
+            ..Default::default()
+        };
+        let report = validate_cloudgeotiff_with_options(&current_dir, &options).unwrap();
+        assert_eq!(
+            report.warnings,
+            vec![Warning::NoThumbnailSizedOverview {
+                smallest_dim: 27,
+                max_dim: 16
+            }]
+        )
+    }
+
+    #[test]
+    fn test_min_overview_levels() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let options = ValidationOptions {
+            min_overview_levels: 4,
+            ..Default::default()
+        };
+        let result = validate_cloudgeotiff_with_options(&current_dir, &options);
+        assert!(matches!(
+            result,
+            Err(ValidateCOGError::TooFewOverviewLevels {
+                found: 0,
+                required: 4
+            })
+        ))
+    }
+
+    #[test]
+    fn test_validate_with_progress() {
+        let mut current_dir = env::current_dir().unwrap();
+        current_dir.push("src/data/PuertoRicoTropicalFruit_cog.tif");
+        let mut last = None;
+        let options = ValidationOptions::default();
+        validate_cloudgeotiff_with_progress(&current_dir, &options, |progress| {
+            last = Some(*progress);
+        })
+        .unwrap();
+        let last = last.unwrap();
+        assert_eq!(last.completed_blocks, last.total_blocks);
+        assert_eq!(last.fraction(), 1.0)
+    }
+
+    #[test]
+    fn test_check_tile_dimensions() {
+        let ifd = |tags| Ifd {
+            offset: 8,
+            tags,
+            next_ifd_offset: 0,
+        };
+        let tiled = |width, height| {
+            ifd(vec![
+                IfdTag::inline(TAG_TILE_WIDTH, 3, width),
+                IfdTag::inline(TAG_TILE_LENGTH, 3, height),
+            ])
+        };
+        assert!(matches!(
+            _check_tile_dimensions(&[tiled(500, 512)]),
+            Err(ValidateCOGError::TileDimensionNotMultipleOf16 {
+                width: 500,
+                height: 512
+            })
+        ));
+        assert!(_check_tile_dimensions(&[tiled(512, 512)]).is_ok());
+        // StripOffsets only
+        let stripped = ifd(vec![IfdTag::inline(273, 4, 384)]);
+        assert!(_check_tile_dimensions(&[stripped]).is_ok());
+    }
+
+    #[test]
+    fn test_check_ifds_before_data() {
+        let header = TiffHeader {
+            byte_order: TiffByteOrder::LittleEndian,
+            big_tiff: false,
+            first_ifd_offset: 8,
+        };
+        let ifd = |offset| Ifd {
+            offset,
+            tags: Vec::new(),
+            next_ifd_offset: 0,
+        };
+        let options = ValidationOptions::strict_spec();
+        let ifds = [ifd(8), ifd(300)];
+        assert!(_check_ifds_before_data(&header, &ifds, &[500], &[400], &options).is_ok());
+        // The tiles of another band start before the last IFD
+        assert!(matches!(
+            _check_ifds_before_data(&header, &ifds, &[500, 200], &[400], &options),
+            Err(ValidateCOGError::IfdsAfterDataError {
+                ifd_offset: 300,
+                data_offset: 200
+            })
+        ));
+    }
+
+    #[test]
+    fn test_band_names() {
+        assert_eq!(_band_name(1), "Main resolution image");
+        assert_eq!(_band_name(3), "Band 3");
+        assert_eq!(_ovr_name(1, 0), "overview_0");
+        assert_eq!(_ovr_name(3, 1), "Band 3 overview_1");
+    }
+
+    #[test]
+    fn test_mask_is_validated() {
+        let per_band = MaskFlags::default();
+        let per_dataset = MaskFlags {
+            per_dataset: true,
+            ..Default::default()
+        };
+        let all_valid = MaskFlags {
+            all_valid: true,
+            ..Default::default()
+        };
+        assert!(_mask_is_validated(&per_band, true));
+        assert!(_mask_is_validated(&per_band, false));
+        assert!(_mask_is_validated(&per_dataset, true));
+        assert!(!_mask_is_validated(&per_dataset, false));
+        assert!(!_mask_is_validated(&all_valid, true));
+    }
+
+    #[test]
+    fn test_data_layout() {
+        assert_eq!(_data_layout(&[500, 600], &[]), DataLayout::NoOverviews);
+        assert_eq!(_data_layout(&[], &[]), DataLayout::NoOverviews);
+        assert_eq!(_data_layout(&[], &[100, 200]), DataLayout::OverviewsOnly);
+        assert_eq!(
+            _data_layout(&[500, 600], &[100, 200]),
+            DataLayout::OverviewsFirst
+        );
+        assert_eq!(
+            _data_layout(&[100, 200], &[500, 600]),
+            DataLayout::BaseFirst
+        );
+        assert_eq!(_data_layout(&[100, 600], &[500]), DataLayout::Interleaved);
+    }
+}